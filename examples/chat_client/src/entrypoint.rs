@@ -54,7 +54,9 @@ pub fn main() -> Result<(), JsValue>
 	{
 		let chat = document().get_element_by_id( "chat" ).expect( "find chat"       );
 
-		let (ws, wsio) = match WsStream::connect( URL, None ).await
+		// This app doesn't speak a named sub protocol, so we don't request one.
+		//
+		let (ws, wsio) = match WsStream::connect( URL, Vec::<&str>::new() ).await
 		{
 			Ok(conn) => conn,
 			Err(e)   =>
@@ -64,7 +66,7 @@ pub fn main() -> Result<(), JsValue>
 			}
 		};
 
-		let framed      = Framed::new( wsio, Codec::new() );
+		let framed      = Framed::new( wsio.into_io(), Codec::new() );
 		let (out, msgs) = framed.split();
 
 		let send    = document().get_element_by_id( "chat_submit" ).expect_throw( "find chat_submit" );
@@ -1,9 +1,221 @@
 use
 {
-	crate :: { import::*, WsErr, WsErrKind, JsMsgEvent, WsMessage, WsState, future_event },
+	crate   :: { import::*, WsErr, WsErrKind, JsMsgEvent, WsMessage, WsState, WsStream, future_event },
+	futures :: { channel::oneshot, future::{ select, Either }, FutureExt, StreamExt, SinkExt, select, ready },
+	std     :: { time::Duration                                                                      },
 };
 
 
+/// Information about how the connection was closed, taken from the browser's
+/// [`CloseEvent`](https://developer.mozilla.org/en-US/docs/Web/API/CloseEvent).
+///
+/// This is captured from the `onclose` callback and made available through [`WsIo::close_event`]
+/// once the connection has gone away, be it through a clean close initiated by either side, or
+/// through an abnormal drop (eg. the server process died, or the network went away).
+//
+#[ derive( Debug, Clone, PartialEq ) ]
+//
+pub struct CloseEvent
+{
+	/// The close code given by the server.
+	//
+	pub code: u16,
+
+	/// The reason given by the server.
+	//
+	pub reason: String,
+
+	/// Whether the connection was closed cleanly (both endpoints properly went through the
+	/// closing handshake, as opposed to eg. the network connection dropping).
+	//
+	pub was_clean: bool,
+}
+
+
+impl From<&web_sys::CloseEvent> for CloseEvent
+{
+	fn from( evt: &web_sys::CloseEvent ) -> Self
+	{
+		Self
+		{
+			code     : evt.code()     ,
+			reason   : evt.reason()   ,
+			was_clean: evt.was_clean(),
+		}
+	}
+}
+
+
+// Checks the close code against the values allowed by RFC 6455 for an application to use
+// when closing the connection itself: https://tools.ietf.org/html/rfc6455#section-7.4.2
+//
+fn validate_close_code( code: u16 ) -> Result<(), WsErr>
+{
+	match code
+	{
+		1000         => Ok(()),
+		3000..=4999  => Ok(()),
+		_            => Err( WsErrKind::InvalidCloseCode(code).into() ),
+	}
+}
+
+
+// The browser throws a SyntaxError if the UTF-8 encoded close reason is over 123 bytes,
+// the most a close frame can carry alongside its 2 byte code in a 125 byte control frame.
+//
+fn validate_close_reason( reason: &str ) -> Result<(), WsErr>
+{
+	match reason.len()
+	{
+		0..=123 => Ok(()),
+		_       => Err( WsErrKind::ReasonStringToLong.into() ),
+	}
+}
+
+
+#[ cfg(test) ]
+//
+mod close_validation_tests
+{
+	use super::*;
+
+	#[test] fn close_code_0_is_invalid     () { assert!( validate_close_code(    0 ).is_err() ); }
+	#[test] fn close_code_999_is_invalid   () { assert!( validate_close_code(  999 ).is_err() ); }
+	#[test] fn close_code_1000_ok          () { assert!( validate_close_code( 1000 ).is_ok () ); }
+	#[test] fn close_code_1001_is_invalid  () { assert!( validate_close_code( 1001 ).is_err() ); }
+	#[test] fn close_code_2999_is_invalid  () { assert!( validate_close_code( 2999 ).is_err() ); }
+	#[test] fn close_code_3000_ok          () { assert!( validate_close_code( 3000 ).is_ok () ); }
+	#[test] fn close_code_4999_ok          () { assert!( validate_close_code( 4999 ).is_ok () ); }
+	#[test] fn close_code_5000_is_invalid  () { assert!( validate_close_code( 5000 ).is_err() ); }
+
+	#[test] fn close_reason_empty_ok       () { assert!( validate_close_reason( "" ).is_ok() ); }
+
+	#[test] fn close_reason_123_bytes_ok()
+	{
+		let reason = "a".repeat(123);
+		assert!( validate_close_reason( &reason ).is_ok() );
+	}
+
+	#[test] fn close_reason_124_bytes_is_invalid()
+	{
+		let reason = "a".repeat(124);
+		assert!( validate_close_reason( &reason ).is_err() );
+	}
+}
+
+
+/// An event on the lifecycle of the connection, as observed through [`WsIo::observe`].
+///
+/// This lets callers react to a connection dying or erroring out without having to
+/// busy-poll [`WsIo::ready_state`].
+//
+#[ derive( Debug, Clone ) ]
+//
+pub enum WsEvent
+{
+	/// The connection has been established and is ready to send/receive messages.
+	//
+	Open,
+
+	/// We started the closing handshake (through [`WsIo::close_with`], closing the [Sink] or
+	/// dropping the [WsIo]). A close initiated by the server is not announced with this event;
+	/// observers then only get [`WsEvent::Closed`].
+	//
+	Closing,
+
+	/// The connection has been closed.
+	//
+	Closed( CloseEvent ),
+
+	/// The browser reported an error on the socket.
+	//
+	Error,
+
+	/// [ReconnectingWsIo] failed to connect, or lost the connection, and is about to retry.
+	/// `attempt` is the number of retries made since the last successful connection (0 for
+	/// the very first retry).
+	//
+	Reconnecting{ attempt: u32 },
+}
+
+
+// A handful of observers subscribed through [WsIo::observe]. We don't try to be clever
+// about unsubscribing; a closed/dropped receiver simply stops being retained the next
+// time we try to notify it. Each observer chooses its own queue size (see
+// [WsIo::observe]), so a slow observer can't grow its channel without bound; it just
+// misses events once its queue is full, rather than stalling or OOMing the sender side.
+//
+type Observers = Rc<RefCell< Vec< mpsc::Sender<WsEvent> > >>;
+
+
+fn notify( observers: &Observers, evt: WsEvent )
+{
+	observers.borrow_mut().iter_mut().for_each( |tx|
+	{
+		// A full queue just means this observer misses this event; only a disconnected
+		// receiver gets dropped from the list (done in a second pass, below).
+		//
+		let _ = tx.try_send( evt.clone() );
+	});
+
+	observers.borrow_mut().retain( |tx| !tx.is_closed() );
+}
+
+
+// Resolve after `ms` milliseconds, using `setTimeout`. Used to race the `onopen` event in
+// [wait_for_open] so [`WsStream::connect_with_timeout`](crate::WsStream::connect_with_timeout)
+// doesn't hang forever on a server that never completes the handshake, and to wait out the
+// backoff delay in [ReconnectingWsIo].
+//
+async fn sleep( ms: i32 )
+{
+	let (tx, rx) = oneshot::channel();
+
+	let cb = Closure::once( Box::new( move || { let _ = tx.send(()); } ) as Box<dyn FnOnce()> );
+
+	web_sys::window().expect_throw( "no window" )
+		.set_timeout_with_callback_and_timeout_and_arguments_0( cb.as_ref().unchecked_ref(), ms )
+		.expect_throw( "WsIo: set connect timeout timer" )
+	;
+
+	cb.forget();
+
+	let _ = rx.await;
+}
+
+
+// Wait for the `onopen` event on `ws`, optionally racing it against `timeout`. Used by
+// [`WsStream::connect_with_timeout`](crate::WsStream::connect_with_timeout). On a timeout,
+// the half-open socket and its `onopen` callback are cleaned up and
+// [WsErrKind::ConnectionTimeout] is returned.
+//
+pub(crate) async fn wait_for_open( ws: &WebSocket, timeout: Option<Duration> ) -> Result<(), WsErr>
+{
+	let opened = future_event( |cb| ws.set_onopen( cb ) );
+
+	match timeout
+	{
+		None => { opened.await; Ok(()) }
+
+		Some( d ) =>
+		{
+			match select( opened.boxed_local(), sleep( d.as_millis() as i32 ).boxed_local() ).await
+			{
+				Either::Left(  _ ) => Ok(()),
+
+				Either::Right( _ ) =>
+				{
+					ws.set_onopen( None );
+					ws.close().expect_throw( "WsIo: close half-open socket on connect timeout" );
+
+					Err( WsErrKind::ConnectionTimeout.into() )
+				}
+			}
+		}
+	}
+}
+
+
 /// A wrapper around [web_sys::WebSocket](https://docs.rs/web-sys/0.3.25/web_sys/struct.WebSocket.html) to make it more rust idiomatic.
 /// It does not provide any extra functionality over the wrapped WebSocket object.
 ///
@@ -58,13 +270,35 @@ use
 //
 pub struct WsIo
 {
-	ws     : WebSocket                                      ,
-	on_mesg: Closure< dyn FnMut( MessageEvent ) + 'static > ,
-	queue  : Rc<RefCell< VecDeque<JsMsgEvent> >>            ,
-	waker  : Rc<RefCell<Option<Waker>>>                     , // TODO: can we use a reference rather than cloning?
+	ws         : WebSocket                                           ,
+	on_mesg    : Closure< dyn FnMut( MessageEvent        ) + 'static >,
+	on_close   : Closure< dyn FnMut( web_sys::CloseEvent  ) + 'static >,
+	on_open    : Closure< dyn FnMut( Event                ) + 'static >,
+	on_error   : Closure< dyn FnMut( web_sys::ErrorEvent  ) + 'static >,
+	queue      : Rc<RefCell< VecDeque<JsMsgEvent> >>                 ,
+	waker      : Rc<RefCell<Option<Waker>>>                          , // TODO: can we use a reference rather than cloning?
+	close_event: Rc<RefCell<Option<CloseEvent>>>                     ,
+	close_waker: Rc<RefCell<Option<Waker>>>                          ,
+	observers  : Observers                                           ,
+	high_water_mark: Cell<u32>                                       ,
 }
 
 
+/// Default high water mark (in bytes of `WebSocket.bufferedAmount`) at which the [Sink]
+/// starts exerting backpressure. Can be changed with [WsIo::set_high_water_mark].
+//
+pub const DEFAULT_HIGH_WATER_MARK: u32 = 1_048_576; // 1 MiB
+
+/// A reasonable default `queue_size` to pass to [WsIo::observe] for an observer that just
+/// wants to react to state changes (as opposed to reliably consuming every event).
+//
+pub const DEFAULT_OBSERVER_QUEUE_SIZE: usize = 16;
+
+// How long to wait before re-checking `bufferedAmount` while the sink is backpressured.
+//
+const BACKPRESSURE_POLL_MS: i32 = 20;
+
+
 impl WsIo
 {
 	/// Create a new WsIo.
@@ -73,9 +307,17 @@ impl WsIo
 	{
 		let waker: Rc<RefCell<Option<Waker>>> = Rc::new( RefCell::new( None ));
 
-		let queue = Rc::new( RefCell::new( VecDeque::new() ) );
-		let q2    = queue.clone();
-		let w2    = waker.clone();
+		let queue       = Rc::new( RefCell::new( VecDeque::new() ) );
+		let close_event = Rc::new( RefCell::new( None ) );
+		let close_waker: Rc<RefCell<Option<Waker>>> = Rc::new( RefCell::new( None ) );
+		let observers: Observers = Rc::new( RefCell::new( Vec::new() ) );
+		let q2          = queue.clone();
+		let w2          = waker.clone();
+		let ce2         = close_event.clone();
+		let cw2         = close_waker.clone();
+		let ob_open     = observers.clone();
+		let ob_close    = observers.clone();
+		let ob_error    = observers.clone();
 
 
 		// Send the incoming ws messages to the WsStream object
@@ -95,17 +337,70 @@ impl WsIo
 		}) as Box< dyn FnMut( MessageEvent ) > );
 
 
-		// Install callback
+		// Capture the close code/reason the server sent us, so callers can still retrieve it
+		// after the stream has ended, and wake up anyone blocked in poll_close.
 		//
-		ws.set_onmessage  ( Some( on_mesg.as_ref().unchecked_ref() ) );
+		let on_close = Closure::wrap( Box::new( move |evt: web_sys::CloseEvent|
+		{
+			trace!( "WsStream: close event received!" );
+
+			let close_event = CloseEvent::from( &evt );
+
+			*ce2.borrow_mut() = Some( close_event.clone() );
+
+			if let Some( w ) = cw2.borrow_mut().take()
+			{
+				w.wake()
+			}
+
+			notify( &ob_close, WsEvent::Closed( close_event ) );
+
+		}) as Box< dyn FnMut( web_sys::CloseEvent ) > );
+
+
+		// Notify observers that the connection is open.
+		//
+		let on_open = Closure::wrap( Box::new( move |_evt: Event|
+		{
+			trace!( "WsStream: open event received!" );
+
+			notify( &ob_open, WsEvent::Open );
+
+		}) as Box< dyn FnMut( Event ) > );
+
+
+		// Notify observers that the browser reported an error on the socket.
+		//
+		let on_error = Closure::wrap( Box::new( move |_evt: web_sys::ErrorEvent|
+		{
+			trace!( "WsStream: error event received!" );
+
+			notify( &ob_error, WsEvent::Error );
+
+		}) as Box< dyn FnMut( web_sys::ErrorEvent ) > );
+
+
+		// Install callbacks
+		//
+		ws.set_onmessage( Some( on_mesg .as_ref().unchecked_ref() ) );
+		ws.set_onclose  ( Some( on_close.as_ref().unchecked_ref() ) );
+		ws.set_onopen   ( Some( on_open .as_ref().unchecked_ref() ) );
+		ws.set_onerror  ( Some( on_error.as_ref().unchecked_ref() ) );
 
 
 		Self
 		{
-			ws      ,
-			queue   ,
-			on_mesg ,
-			waker   ,
+			ws         ,
+			queue      ,
+			on_mesg    ,
+			on_close   ,
+			on_open    ,
+			on_error   ,
+			waker      ,
+			close_event,
+			close_waker,
+			observers  ,
+			high_water_mark: Cell::new( DEFAULT_HIGH_WATER_MARK ),
 		}
 	}
 
@@ -121,14 +416,152 @@ impl WsIo
 
 
 
-	// This method allows to do async close in the poll_close of Sink
+	/// The sub protocol the server selected, as negotiated through the list passed to
+	/// [`WsStream::connect`](crate::WsStream::connect). Empty if none was requested or
+	/// the server didn't select one.
 	//
-	async fn wake_on_close( ws: WebSocket, waker: Waker )
+	pub fn protocol( &self ) -> String
 	{
-		future_event( |cb| ws.set_onclose( cb ) ).await;
+		self.ws.protocol()
+	}
+
 
-		waker.wake();
+
+	/// The extensions selected by the server during the handshake (eg. `permessage-deflate`).
+	//
+	pub fn extensions( &self ) -> String
+	{
+		self.ws.extensions()
 	}
+
+
+
+	// Called by [`WsStream::connect`](crate::WsStream::connect) right after the connection opens.
+	// `protocol_requested` must be the caller's own `!protocols.is_empty()`: this method has no
+	// visibility into what was requested, so the caller must say explicitly whether an empty
+	// `self.protocol()` means "none was requested" (fine) or "one was requested but the server
+	// didn't pick one" (an error).
+	//
+	pub(crate) fn ensure_protocol( &self, protocol_requested: bool ) -> Result<(), WsErr>
+	{
+		if protocol_requested && self.protocol().is_empty()
+		{
+			Err( WsErrKind::NoProtocolSelected.into() )
+		}
+
+		else
+		{
+			Ok(())
+		}
+	}
+
+
+
+	/// Close the socket, sending the given close code and reason to the server, rather than
+	/// the browser default (1000, no reason) that [`poll_close`](Sink::poll_close) and
+	/// [`Drop`] use.
+	///
+	/// The code must be `1000`, or in the `3000..=4999` range reserved for applications, as
+	/// mandated by [RFC 6455 section 7.4.2](https://tools.ietf.org/html/rfc6455#section-7.4.2).
+	/// Any other value, including the ones reserved for the protocol itself, returns
+	/// [`WsErrKind::InvalidCloseCode`].
+	///
+	/// `reason` must encode to 123 bytes or fewer in UTF-8, or the browser throws a
+	/// `SyntaxError`; a longer reason returns [`WsErrKind::ReasonStringToLong`] instead.
+	//
+	pub fn close_with( &self, code: u16, reason: &str ) -> Result<(), WsErr>
+	{
+		validate_close_code  ( code   )?;
+		validate_close_reason( reason )?;
+
+		notify( &self.observers, WsEvent::Closing );
+
+		self.ws.close_with_code_and_reason( code, reason ).expect( "WsIo::close_with - close ws socket" );
+
+		Ok(())
+	}
+
+
+
+	/// The [CloseEvent] the server sent when the connection closed, if any. This is only
+	/// available once the connection has actually closed (see [ready_state](WsIo::ready_state)),
+	/// and will be `None` if the stream was dropped before ever reaching the `Closed` state.
+	//
+	pub fn close_event( &self ) -> Option<CloseEvent>
+	{
+		self.close_event.borrow().clone()
+	}
+
+
+
+	/// Subscribe to the lifecycle of this connection. Every call returns a new
+	/// [`Stream`] of [WsEvent], so several independent observers can coexist (eg. a UI
+	/// layer showing connection status next to application code reacting to an error).
+	///
+	/// `queue_size` bounds how many events this observer can be behind by before it starts
+	/// missing them; a lagging or dropped observer only ever misses events, it never makes
+	/// notifying other observers slower or grows memory without bound. See
+	/// [DEFAULT_OBSERVER_QUEUE_SIZE] for a reasonable default.
+	//
+	pub fn observe( &self, queue_size: usize ) -> mpsc::Receiver<WsEvent>
+	{
+		let (tx, rx) = mpsc::channel( queue_size );
+
+		self.observers.borrow_mut().push( tx );
+
+		rx
+	}
+
+
+
+	/// The high water mark (in bytes of `WebSocket.bufferedAmount`) at which the [Sink]
+	/// starts exerting backpressure. Defaults to [DEFAULT_HIGH_WATER_MARK].
+	//
+	pub fn high_water_mark( &self ) -> u32
+	{
+		self.high_water_mark.get()
+	}
+
+
+
+	/// Change the high water mark used to backpressure the [Sink]. See [high_water_mark](WsIo::high_water_mark).
+	//
+	pub fn set_high_water_mark( &self, bytes: u32 )
+	{
+		self.high_water_mark.set( bytes );
+	}
+
+
+
+	// Re-poll the task once `bufferedAmount` has had a chance to drain. The web platform
+	// gives us no event for this, so we fall back to a short timer.
+	//
+	fn schedule_wake( waker: Waker )
+	{
+		let cb = Closure::once( Box::new( move || waker.wake() ) as Box<dyn FnOnce()> );
+
+		web_sys::window().expect_throw( "no window" )
+			.set_timeout_with_callback_and_timeout_and_arguments_0( cb.as_ref().unchecked_ref(), BACKPRESSURE_POLL_MS )
+			.expect_throw( "WsIo: schedule backpressure timer" )
+		;
+
+		cb.forget();
+	}
+
+
+
+	/// Turn this `WsIo` into a [futures::io::AsyncRead] / [futures::io::AsyncWrite] adapter,
+	/// treating each binary message as a chunk of a continuous byte stream. This allows
+	/// driving byte-oriented codecs (eg. [futures_codec::Framed]) directly over the socket.
+	///
+	/// Text messages are not representable as bytes; receiving one makes the read side
+	/// error out with `io::ErrorKind::InvalidData`.
+	//
+	pub fn into_io( self ) -> WsStreamIo
+	{
+		WsStreamIo::new( self )
+	}
+
 }
 
 
@@ -159,6 +592,23 @@ impl Drop for WsIo
 	{
 		trace!( "Drop WsIo" );
 
+		let state = self.ready_state();
+
+		// Only announce Closing if we are the ones initiating it here. If the socket
+		// already reached Closed, observers already got WsEvent::Closed, and notifying
+		// Closing now would put it after Closed, out of order. Mirrors the gating
+		// in poll_close.
+		//
+		if state == WsState::Connecting
+		|| state == WsState::Open
+		{
+			notify( &self.observers, WsEvent::Closing );
+		}
+
+		// We have no way to run a close code/reason past the caller here, so fall back to
+		// the browser default. Use [close_with](WsIo::close_with) before dropping if that
+		// matters to the application.
+		//
 		self.ws.close().expect( "WsIo::drop - close ws socket" );
 	}
 }
@@ -211,15 +661,31 @@ impl Sink<WsMessage> for WsIo
 
 	// Web api does not really seem to let us check for readiness, other than the connection state.
 	//
-	fn poll_ready( self: Pin<&mut Self>, _: &mut Context ) -> Poll<Result<(), Self::Error>>
+	fn poll_ready( self: Pin<&mut Self>, cx: &mut Context ) -> Poll<Result<(), Self::Error>>
 	{
 		trace!( "Sink<WsMessage> for WsIo: poll_ready" );
 
 		match self.ready_state()
 		{
 			WsState::Connecting => Poll::Pending        ,
-			WsState::Open       => Poll::Ready( Ok(()) ),
-			_                   => Poll::Ready( Err( WsErrKind::ConnectionClosed.into() )),
+
+			WsState::Open =>
+			{
+				if self.ws.buffered_amount() > self.high_water_mark.get()
+				{
+					trace!( "WsIo: backpressured, bufferedAmount over high water mark" );
+
+					Self::schedule_wake( cx.waker().clone() );
+					Poll::Pending
+				}
+
+				else
+				{
+					Poll::Ready( Ok(()) )
+				}
+			}
+
+			_ => Poll::Ready( Err( WsErrKind::ConnectionClosed.into() )),
 		}
 	}
 
@@ -252,17 +718,35 @@ impl Sink<WsMessage> for WsIo
 
 
 
-	fn poll_flush( self: Pin<&mut Self>, _: &mut Context ) -> Poll<Result<(), Self::Error>>
+	fn poll_flush( self: Pin<&mut Self>, cx: &mut Context ) -> Poll<Result<(), Self::Error>>
 	{
 		trace!( "Sink<WsMessage> for WsIo: poll_flush" );
 
-		Poll::Ready( Ok(()) )
+		// bufferedAmount isn't guaranteed to drain to 0 once the socket leaves Open (eg.
+		// the connection dropped abnormally with unsent data still queued), so gate on
+		// ready_state the same way poll_ready does rather than looping forever.
+		//
+		match self.ready_state()
+		{
+			WsState::Open =>
+			{
+				if self.ws.buffered_amount() > 0
+				{
+					Self::schedule_wake( cx.waker().clone() );
+					return Poll::Pending;
+				}
+
+				Poll::Ready( Ok(()) )
+			}
+
+			WsState::Connecting => Poll::Pending,
+
+			_ => Poll::Ready( Err( WsErrKind::ConnectionClosed.into() )),
+		}
 	}
 
 
 
-	// TODO: find a simpler implementation, notably this needs to clone the websocket and spawn a future.
-	//
 	fn poll_close( self: Pin<&mut Self>, cx: &mut Context ) -> Poll<Result<(), Self::Error>>
 	{
 		trace!( "Sink<WsMessage> for WsIo: poll_close" );
@@ -273,6 +757,8 @@ impl Sink<WsMessage> for WsIo
 		if state == WsState::Connecting
 		|| state == WsState::Open
 		{
+			notify( &self.observers, WsEvent::Closing );
+
 			self.ws.close().unwrap_throw();
 		}
 
@@ -287,7 +773,7 @@ impl Sink<WsMessage> for WsIo
 
 			_ =>
 			{
-				rt::spawn_local( Self::wake_on_close( self.ws.clone(), cx.waker().clone() ) ).expect( "spawn wake_on_close" );
+				*self.close_waker.borrow_mut() = Some( cx.waker().clone() );
 				Poll::Pending
 			}
 		}
@@ -295,6 +781,489 @@ impl Sink<WsMessage> for WsIo
 }
 
 
+// Where we are in the current incoming message, if any. `WsStreamIo` pulls a new
+// `WsMessage::Binary` from the underlying `WsIo` stream every time the cursor runs dry.
+//
+enum ReadState
+{
+	PendingChunk                  ,
+	Ready( Cursor<Vec<u8>> )      ,
+	Eof                           ,
+}
+
+
+/// Adapts a [WsIo] into [futures::io::AsyncRead] + [futures::io::AsyncWrite] +
+/// [futures::io::AsyncBufRead], treating each binary websocket message as a chunk of a
+/// continuous byte stream. Created through [WsIo::into_io].
+///
+/// This makes `ws_stream_wasm` usable as a transport for any `AsyncRead + AsyncWrite`
+/// based protocol, such as length-delimited codecs or TLS.
+//
+pub struct WsStreamIo
+{
+	inner     : WsIo     ,
+	read_state: ReadState,
+	write_buf : Vec<u8>  ,
+}
+
+
+impl WsStreamIo
+{
+	fn new( inner: WsIo ) -> Self
+	{
+		Self
+		{
+			inner                              ,
+			read_state: ReadState::PendingChunk,
+			write_buf : Vec::new()             ,
+		}
+	}
+}
+
+
+impl AsyncRead for WsStreamIo
+{
+	// Implemented in terms of AsyncBufRead, like most adapters: poll_fill_buf alone is
+	// responsible for advancing `read_state`, so there's a single place that decides
+	// "cursor exhausted" vs. "caller's buffer is merely empty/smaller than what's available".
+	//
+	fn poll_read( mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8] ) -> Poll<io::Result<usize>>
+	{
+		let available = ready!( self.as_mut().poll_fill_buf( cx ) )?;
+		let n         = available.len().min( buf.len() );
+
+		buf[ ..n ].copy_from_slice( &available[ ..n ] );
+
+		self.consume( n );
+
+		Poll::Ready( Ok( n ) )
+	}
+}
+
+
+impl AsyncBufRead for WsStreamIo
+{
+	fn poll_fill_buf( self: Pin<&mut Self>, cx: &mut Context ) -> Poll<io::Result<&[u8]>>
+	{
+		let this = self.get_mut();
+
+		loop
+		{
+			match &this.read_state
+			{
+				ReadState::Eof             => return Poll::Ready( Ok( &[][..] ) ),
+				ReadState::Ready( cursor ) if ( cursor.position() as usize ) < cursor.get_ref().len() =>
+				{
+					let pos = cursor.position() as usize;
+					return Poll::Ready( Ok( &cursor.get_ref()[ pos.. ] ) );
+				}
+
+				// Cursor absent or exhausted: pull the next message in ourselves, rather
+				// than going through poll_read with a dummy buffer (which can't tell an
+				// exhausted cursor from one that just wasn't given anywhere to write).
+				//
+				_ =>
+				{
+					match Pin::new( &mut this.inner ).poll_next( cx )
+					{
+						Poll::Pending       => return Poll::Pending,
+						Poll::Ready( None ) => { this.read_state = ReadState::Eof; }
+
+						Poll::Ready( Some( WsMessage::Binary( d ) ) ) =>
+						{
+							this.read_state = ReadState::Ready( Cursor::new( d ) );
+						}
+
+						Poll::Ready( Some( WsMessage::Text( _ ) ) ) => return Poll::Ready( Err
+						(
+							io::Error::new( io::ErrorKind::InvalidData, "received a text message on a byte stream" )
+						)),
+					}
+				}
+			}
+		}
+	}
+
+
+	fn consume( self: Pin<&mut Self>, amt: usize )
+	{
+		if let ReadState::Ready( cursor ) = &mut self.get_mut().read_state
+		{
+			let new_pos = cursor.position() + amt as u64;
+			cursor.set_position( new_pos );
+		}
+	}
+}
+
+
+impl AsyncWrite for WsStreamIo
+{
+	fn poll_write( mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8] ) -> Poll<io::Result<usize>>
+	{
+		match Pin::new( &mut self.inner ).poll_ready( cx )
+		{
+			Poll::Pending     => return Poll::Pending,
+			Poll::Ready(Err(e)) => return Poll::Ready( Err( io::Error::new( io::ErrorKind::Other, e ) ) ),
+			Poll::Ready(Ok(())) => {}
+		}
+
+		self.write_buf.extend_from_slice( buf );
+
+		Poll::Ready( Ok( buf.len() ) )
+	}
+
+
+	// Flush the buffered bytes as a single binary message.
+	//
+	fn poll_flush( mut self: Pin<&mut Self>, cx: &mut Context ) -> Poll<io::Result<()>>
+	{
+		if !self.write_buf.is_empty()
+		{
+			let msg = WsMessage::Binary( std::mem::take( &mut self.write_buf ) );
+
+			Pin::new( &mut self.inner ).start_send( msg ).map_err( |e| io::Error::new( io::ErrorKind::Other, e ) )?;
+		}
+
+		Pin::new( &mut self.inner ).poll_flush( cx ).map_err( |e| io::Error::new( io::ErrorKind::Other, e ) )
+	}
+
+
+	fn poll_close( mut self: Pin<&mut Self>, cx: &mut Context ) -> Poll<io::Result<()>>
+	{
+		match Pin::new( &mut self ).poll_flush( cx )
+		{
+			Poll::Pending      => return Poll::Pending,
+			Poll::Ready(Err(e)) => return Poll::Ready( Err(e) ),
+			Poll::Ready(Ok(())) => {}
+		}
+
+		Pin::new( &mut self.inner ).poll_close( cx ).map_err( |e| io::Error::new( io::ErrorKind::Other, e ) )
+	}
+}
+
+
+/// Tuning for the exponential backoff [ReconnectingWsIo] uses between reconnect attempts.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct ReconnectConfig
+{
+	/// Delay before the first reconnect attempt.
+	//
+	pub base_delay: Duration,
+
+	/// Upper bound the exponential delay is capped to, regardless of how many attempts failed.
+	/// Jitter is added after capping, so the actual delay can exceed this by up to `jitter`
+	/// times this value.
+	//
+	pub max_delay: Duration,
+
+	/// Fraction (`0.0..=1.0`) of the computed delay to randomize by, so that many clients
+	/// reconnecting to the same server don't all retry in lockstep.
+	//
+	pub jitter: f64,
+}
+
+
+impl Default for ReconnectConfig
+{
+	fn default() -> Self
+	{
+		Self
+		{
+			base_delay: Duration::from_millis( 250 ),
+			max_delay : Duration::from_secs ( 30   ),
+			jitter    : 0.2                         ,
+		}
+	}
+}
+
+
+impl ReconnectConfig
+{
+	fn delay_for( &self, attempt: u32 ) -> Duration
+	{
+		let exp    = self.base_delay.as_millis().saturating_mul( 1u128 << attempt.min( 16 ) );
+		let capped = exp.min( self.max_delay.as_millis() ) as f64;
+		let jitter = capped * self.jitter * js_sys::Math::random();
+
+		Duration::from_millis( ( capped + jitter ) as u64 )
+	}
+}
+
+
+#[ cfg(test) ]
+//
+mod reconnect_config_tests
+{
+	use super::*;
+
+	fn no_jitter() -> ReconnectConfig
+	{
+		ReconnectConfig{ jitter: 0.0, ..ReconnectConfig::default() }
+	}
+
+	#[test] fn delay_for_attempt_0_is_base_delay()
+	{
+		let config = no_jitter();
+		assert_eq!( config.delay_for(0), config.base_delay );
+	}
+
+	#[test] fn delay_for_doubles_each_attempt()
+	{
+		let config = no_jitter();
+		assert_eq!( config.delay_for(1), config.base_delay * 2 );
+		assert_eq!( config.delay_for(2), config.base_delay * 4 );
+	}
+
+	#[test] fn delay_for_is_capped_at_max_delay()
+	{
+		let config = no_jitter();
+		assert_eq!( config.delay_for(10), config.max_delay );
+	}
+
+	#[test] fn delay_for_does_not_overflow_on_large_attempt()
+	{
+		let config = no_jitter();
+		assert_eq!( config.delay_for(u32::MAX), config.max_delay );
+	}
+
+	#[test] fn delay_for_jitter_stays_within_bounds()
+	{
+		let config = ReconnectConfig{ jitter: 0.2, ..ReconnectConfig::default() };
+		let delay  = config.delay_for(10);
+
+		assert!( delay >= config.max_delay                                                       );
+		assert!( delay <= config.max_delay + config.max_delay.mul_f64( config.jitter )            );
+	}
+}
+
+
+/// A [WsIo]-like [Stream]/[Sink] that transparently reconnects to the same URL (with
+/// exponential backoff) whenever the underlying connection is lost, instead of ending the
+/// stream. Useful for long-lived browser sessions (chat apps, live dashboards, the
+/// ethers-rs WASM provider, ...) that would otherwise have to hand-roll reconnection on
+/// top of [WsIo].
+///
+/// Since the server has no memory of a dropped connection, callers almost always need to
+/// re-send some handshake/subscription messages after each reconnect; the `on_reconnect`
+/// hook passed to [connect](ReconnectingWsIo::connect) is called with a sender that feeds
+/// straight back into this `Sink`, for exactly that purpose.
+///
+/// Reconnection attempts are surfaced as [`WsEvent::Reconnecting`] through [observe](ReconnectingWsIo::observe),
+/// so a UI can show "reconnecting...".
+//
+pub struct ReconnectingWsIo
+{
+	outgoing : mpsc::UnboundedSender  <WsMessage>,
+	incoming : mpsc::UnboundedReceiver<WsMessage>,
+	observers: Observers                         ,
+}
+
+
+impl ReconnectingWsIo
+{
+	/// Connect to `url`, requesting `protocols` as sub protocols, and keep reconnecting
+	/// according to `config` for as long as this `ReconnectingWsIo` (or its `Sink` half)
+	/// is alive. `on_reconnect` is called, with a sender that feeds back into this `Sink`,
+	/// every time a connection (including the first one) is successfully (re-)established.
+	//
+	pub fn connect
+	(
+		url         : impl Into<String>                                          ,
+		protocols   : impl IntoIterator<Item = String>                           ,
+		config      : ReconnectConfig                                            ,
+		on_reconnect: impl FnMut( &mpsc::UnboundedSender<WsMessage> ) + 'static ,
+
+	) -> Self
+	{
+		let url       = url.into();
+		let protocols = protocols.into_iter().collect::<Vec<_>>();
+		let observers: Observers = Rc::new( RefCell::new( Vec::new() ) );
+
+		let (out_tx   , out_rx   ) = mpsc::unbounded::<WsMessage>();
+		let (in_tx    , in_rx    ) = mpsc::unbounded::<WsMessage>();
+
+		// A channel private to the driver task, so that `on_reconnect` can feed messages
+		// back into the live connection without the driver having to hold a clone of the
+		// public `out_tx` (see [drive] for why that matters for shutdown).
+		//
+		let (replay_tx, replay_rx) = mpsc::unbounded::<WsMessage>();
+
+		let driver_obs = observers.clone();
+
+		rt::spawn_local( Self::drive( url, protocols, config, out_rx, replay_rx, replay_tx, in_tx, driver_obs, on_reconnect ) )
+
+			.expect( "spawn ReconnectingWsIo driver" );
+
+		Self{ outgoing: out_tx, incoming: in_rx, observers }
+	}
+
+
+	// Owns the actual connection for as long as this ReconnectingWsIo lives: (re)connects,
+	// forwards messages in both directions, and on disconnect waits out the backoff delay
+	// before trying again.
+	//
+	// Deliberately does NOT hold a clone of the public `out_tx` sender: if it did,
+	// `out_rx.next()` could never observe the channel as closed, so dropping (or closing)
+	// the public `ReconnectingWsIo` would never stop this task.
+	//
+	async fn drive
+	(
+		url            : String                                                    ,
+		protocols      : Vec<String>                                               ,
+		config         : ReconnectConfig                                           ,
+		mut out_rx     : mpsc::UnboundedReceiver<WsMessage>                        ,
+		mut replay_rx  : mpsc::UnboundedReceiver<WsMessage>                        ,
+		replay_tx      : mpsc::UnboundedSender  <WsMessage>                        ,
+		in_tx          : mpsc::UnboundedSender  <WsMessage>                        ,
+		observers      : Observers                                                 ,
+		mut on_reconnect: impl FnMut( &mpsc::UnboundedSender<WsMessage> ) + 'static ,
+	)
+	{
+		let mut attempt = 0u32;
+
+		'reconnect: loop
+		{
+			// The public ReconnectingWsIo was dropped while we were disconnected: stop
+			// dialing rather than retrying for nobody.
+			//
+			if in_tx.is_closed() { return; }
+
+			let connected = WsStream::connect( &url, protocols.iter().map( String::as_str ) ).await;
+
+			let (_meta, wsio) = match connected
+			{
+				Ok ( conn ) => conn,
+
+				Err( _err ) =>
+				{
+					notify( &observers, WsEvent::Reconnecting{ attempt } );
+					sleep( config.delay_for( attempt ).as_millis() as i32 ).await;
+					attempt = attempt.saturating_add( 1 );
+
+					continue 'reconnect;
+				}
+			};
+
+			attempt = 0;
+			on_reconnect( &replay_tx );
+
+			let (mut sink, mut stream) = wsio.split();
+
+			loop
+			{
+				select!
+				{
+					outgoing = out_rx.next() =>
+					{
+						match outgoing
+						{
+							Some( msg ) => { let _ = sink.send( msg ).await; }
+
+							// The public ReconnectingWsIo (and its Sink half) was dropped;
+							// nothing will ever send on this channel again, so there is no
+							// point keeping the connection or this task alive.
+							//
+							None => return,
+						}
+					}
+
+					replayed = replay_rx.next() =>
+					{
+						if let Some( msg ) = replayed
+						{
+							let _ = sink.send( msg ).await;
+						}
+					}
+
+					incoming = stream.next() =>
+					{
+						match incoming
+						{
+							Some( msg ) => { let _ = in_tx.unbounded_send( msg ); }
+
+							// The connection died; fall through to reconnect, below.
+							//
+							None => break,
+						}
+					}
+				}
+			}
+
+			// Whether we got here via a clean server-initiated close or an abnormal drop,
+			// the connection is gone: apply the same backoff/jitter and "reconnecting..."
+			// notification as a failed initial dial, rather than hammering the server.
+			//
+			notify( &observers, WsEvent::Reconnecting{ attempt } );
+			sleep( config.delay_for( attempt ).as_millis() as i32 ).await;
+			attempt = attempt.saturating_add( 1 );
+		}
+	}
+
+
+	/// Subscribe to the reconnection attempts of this connection, as [`WsEvent::Reconnecting`].
+	/// The other [WsEvent]s of the underlying connections are not forwarded here. See
+	/// [WsIo::observe].
+	//
+	pub fn observe( &self, queue_size: usize ) -> mpsc::Receiver<WsEvent>
+	{
+		let (tx, rx) = mpsc::channel( queue_size );
+
+		self.observers.borrow_mut().push( tx );
+
+		rx
+	}
+}
+
+
+impl Stream for ReconnectingWsIo
+{
+	type Item = WsMessage;
+
+	fn poll_next( self: Pin<&mut Self>, cx: &mut Context ) -> Poll<Option<Self::Item>>
+	{
+		Pin::new( &mut self.get_mut().incoming ).poll_next( cx )
+	}
+}
+
+
+impl Sink<WsMessage> for ReconnectingWsIo
+{
+	type Error = WsErr;
+
+	fn poll_ready( self: Pin<&mut Self>, _: &mut Context ) -> Poll<Result<(), Self::Error>>
+	{
+		if self.outgoing.is_closed()
+		{
+			Poll::Ready( Err( WsErrKind::ConnectionClosed.into() ) )
+		}
+
+		else
+		{
+			Poll::Ready( Ok(()) )
+		}
+	}
+
+
+	fn start_send( self: Pin<&mut Self>, item: WsMessage ) -> Result<(), Self::Error>
+	{
+		self.outgoing.unbounded_send( item ).map_err( |_| WsErrKind::ConnectionClosed.into() )
+	}
+
+
+	fn poll_flush( self: Pin<&mut Self>, _: &mut Context ) -> Poll<Result<(), Self::Error>>
+	{
+		Poll::Ready( Ok(()) )
+	}
+
+
+	fn poll_close( self: Pin<&mut Self>, _: &mut Context ) -> Poll<Result<(), Self::Error>>
+	{
+		Poll::Ready( Ok(()) )
+	}
+}
+
 
 
 